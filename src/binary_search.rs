@@ -1,29 +1,46 @@
-use std::cmp::Ordering;
 use std::fmt::Debug;
 
 pub fn binary_search<T>(slice: &[T], value: &T) -> Option<usize>
 where
     T: Eq + Ord + Debug,
 {
-    let mut lo = 0;
-    let mut hi = slice.len() - 1;
+    let index = lower_bound(slice, value);
+    if index < slice.len() && &slice[index] == value {
+        Some(index)
+    } else {
+        None
+    }
+}
 
-    while lo <= hi {
-        let mid = (lo + hi) >> 1;
-        let focused = &slice[mid];
+pub fn partition_point<T, F>(slice: &[T], mut pred: F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    let mut lo = 0;
+    let mut hi = slice.len();
 
-        match focused.cmp(value) {
-            Ordering::Equal => return Some(mid),
-            Ordering::Less => lo = mid + 1,
-            Ordering::Greater => hi = mid.wrapping_sub(1),
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(&slice[mid]) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
         }
     }
-    None
+    lo
+}
+
+pub fn lower_bound<T: Ord>(slice: &[T], value: &T) -> usize {
+    partition_point(slice, |item| item < value)
+}
+
+pub fn upper_bound<T: Ord>(slice: &[T], value: &T) -> usize {
+    partition_point(slice, |item| item <= value)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::binary_search;
+    use super::{binary_search, lower_bound, partition_point, upper_bound};
     use proptest::prelude::*;
 
     proptest! {
@@ -39,6 +56,30 @@ mod tests {
             let existing = v.binary_search(&t).ok();
             assert_eq!(actual, existing);
         }
+
+        #[test]
+        fn lower_bound_matches_partition_point_on_less_than(v in sorted_vec::<i32>(1000), t in any::<i32>()) {
+            assert_eq!(lower_bound(&v, &t), v.partition_point(|item| item < &t));
+        }
+
+        #[test]
+        fn upper_bound_matches_partition_point_on_less_than_or_equal(v in sorted_vec::<i32>(1000), t in any::<i32>()) {
+            assert_eq!(upper_bound(&v, &t), v.partition_point(|item| item <= &t));
+        }
+
+        #[test]
+        fn upper_bound_minus_lower_bound_counts_occurrences(v in sorted_vec::<i32>(1000), t in any::<i32>()) {
+            let occurrences = upper_bound(&v, &t) - lower_bound(&v, &t);
+            assert_eq!(occurrences, v.iter().filter(|item| **item == t).count());
+        }
+    }
+
+    #[test]
+    fn bounds_on_an_empty_slice_return_zero() {
+        let empty: Vec<i32> = Vec::new();
+        assert_eq!(lower_bound(&empty, &0), 0);
+        assert_eq!(upper_bound(&empty, &0), 0);
+        assert_eq!(partition_point(&empty, |_| true), 0);
     }
 
     fn index_and_sorted_vec<T>(max_size: usize) -> impl Strategy<Value = (usize, Vec<T>)>
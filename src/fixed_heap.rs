@@ -0,0 +1,153 @@
+use crate::heap_core::{self, HeapStore};
+use std::fmt::Debug;
+
+/// Fixed-capacity, array-backed heap that never touches the allocator.
+///
+/// Note: this does *not* implement `bytemuck::Pod`/`Zeroable`, so it cannot
+/// be memory-mapped or `bytemuck::cast`-ed into a flat byte buffer as a
+/// whole struct. `#[repr(C)]` alone does not make that safe to hand-roll:
+/// the `[T; N]` / `usize` / `u8` fields generally leave padding (e.g.
+/// `FixedHeap<i32, 3>` is 32 bytes for 21 bytes worth of fields), and
+/// exposing that padding as readable bytes via `bytes_of`/`cast_slice` can
+/// leak uninitialized memory. A packed, padding-free layout would need to
+/// special-case alignment per `T`, which this crate doesn't do. The live
+/// elements are exposed only through `iter()`, one `&T` at a time; there is
+/// no accessor that returns them as a single slice.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct FixedHeap<T, const N: usize> {
+    elements: [T; N],
+    size: usize,
+    is_max: u8,
+}
+
+impl<T, const N: usize> HeapStore<T> for FixedHeap<T, N>
+where
+    T: Ord,
+{
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.elements.swap(i, j);
+    }
+
+    fn satisfies(&self, parent_index: usize, child_index: usize) -> bool {
+        if self.is_max != 0 {
+            self.elements[parent_index] >= self.elements[child_index]
+        } else {
+            self.elements[parent_index] <= self.elements[child_index]
+        }
+    }
+}
+
+impl<T: Ord + Default, const N: usize> FixedHeap<T, N> {
+    pub fn new_min() -> FixedHeap<T, N> {
+        FixedHeap::new(false)
+    }
+
+    pub fn new_max() -> FixedHeap<T, N> {
+        FixedHeap::new(true)
+    }
+
+    fn new(is_max: bool) -> FixedHeap<T, N> {
+        Self {
+            elements: std::array::from_fn(|_| T::default()),
+            size: 0,
+            is_max: is_max as u8,
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> FixedHeap<T, N> {
+    pub fn insert(&mut self, value: T) -> Result<(), T> {
+        if self.size == N {
+            return Err(value);
+        }
+        self.elements[self.size] = value;
+        if self.size > 0 {
+            heap_core::shift_up(self, self.size);
+        }
+        self.size += 1;
+        Ok(())
+    }
+
+    pub fn find_top(&self) -> Option<&T> {
+        if self.size == 0 {
+            None
+        } else {
+            self.elements.first()
+        }
+    }
+
+    pub fn extract_top(&mut self) -> Option<T>
+    where
+        T: Default,
+    {
+        if self.size == 0 {
+            return None;
+        }
+        self.size -= 1;
+        self.elements.swap(0, self.size);
+        let result = std::mem::take(&mut self.elements[self.size]);
+        if self.size > 0 {
+            heap_core::shift_down(self, 0);
+        }
+        Some(result)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.elements.iter().take(self.size)
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for FixedHeap<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FixedHeap")
+            .field("elements", &&self.elements[0..self.size])
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::heap_core::check_heap_property_for_all_parents_and_their_children;
+    use proptest::prelude::*;
+
+    const CAPACITY: usize = 64;
+
+    proptest! {
+        #[test]
+        fn heap_property_should_hold_after_inserts(values in proptest::collection::vec(any::<i32>(), 0..=CAPACITY)) {
+            let mut heap = FixedHeap::<i32, CAPACITY>::new_min();
+            for value in values {
+                heap.insert(value).unwrap();
+            }
+            check_heap_property_for_all_parents_and_their_children(&heap);
+        }
+
+        #[test]
+        fn extracting_top_item_should_keep_heap_property(values in proptest::collection::vec(any::<i32>(), 0..=CAPACITY)) {
+            let mut heap = FixedHeap::<i32, CAPACITY>::new_max();
+            for value in values {
+                heap.insert(value).unwrap();
+            }
+            while heap.extract_top().is_some() {
+                check_heap_property_for_all_parents_and_their_children(&heap);
+            }
+        }
+    }
+
+    #[test]
+    fn insert_past_capacity_returns_the_rejected_value() {
+        let mut heap = FixedHeap::<i32, 2>::new_min();
+        heap.insert(1).unwrap();
+        heap.insert(2).unwrap();
+
+        assert_eq!(heap.insert(3), Err(3));
+    }
+}
@@ -1,58 +1,103 @@
+use crate::heap_core::{self, Comparator, HeapStore};
+use std::cmp::Ordering;
 use std::fmt::Debug;
 
 pub struct Heap<T> {
     elements: Vec<T>,
     size: usize,
-    parent_child_relation: ParentChildRelation,
+    comparator: Comparator<T>,
+    kind: Kind,
 }
 
-#[derive(Clone, Copy, Debug)]
-enum ParentChildRelation {
-    Greater,
-    Smaller,
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Kind {
+    Min,
+    Max,
+    Custom,
 }
 
-impl ParentChildRelation {
-    fn rel<T: Ord>(&self, parent: &T, child: &T) -> bool {
-        match *self {
-            ParentChildRelation::Smaller => parent <= child,
-            ParentChildRelation::Greater => parent >= child,
-        }
+impl<T> HeapStore<T> for Heap<T> {
+    fn len(&self) -> usize {
+        self.size
     }
-}
-
-#[inline]
-fn parent_of(child_index: usize) -> usize {
-    child_index.checked_sub(1).unwrap_or_default() / 2
-}
 
-#[inline]
-fn left_child_of(parent_index: usize) -> usize {
-    parent_index * 2 + 1
-}
+    fn swap(&mut self, i: usize, j: usize) {
+        self.elements.swap(i, j);
+    }
 
-#[inline]
-fn right_child_of(parent_index: usize) -> usize {
-    parent_index * 2 + 2
+    fn satisfies(&self, parent_index: usize, child_index: usize) -> bool {
+        (self.comparator)(&self.elements[parent_index], &self.elements[child_index]) != Ordering::Less
+    }
 }
 
 impl<T: Ord> Heap<T> {
     pub fn new_min(capacity: usize) -> Heap<T> {
-        Heap::new(capacity, ParentChildRelation::Smaller)
+        Heap::with_kind(capacity, Kind::Min, |a: &T, b: &T| b.cmp(a))
     }
 
     pub fn new_max(capacity: usize) -> Heap<T> {
-        Heap::new(capacity, ParentChildRelation::Greater)
+        Heap::with_kind(capacity, Kind::Max, |a: &T, b: &T| a.cmp(b))
+    }
+
+    pub fn from_vec_min(elements: Vec<T>) -> Heap<T> {
+        Heap::from_vec_with_kind(elements, Kind::Min, |a: &T, b: &T| b.cmp(a))
     }
 
-    fn new(capacity: usize, parent_child_relation: ParentChildRelation) -> Self {
+    pub fn from_vec_max(elements: Vec<T>) -> Heap<T> {
+        Heap::from_vec_with_kind(elements, Kind::Max, |a: &T, b: &T| a.cmp(b))
+    }
+}
+
+impl<T> Heap<T> {
+    pub fn new_by<F>(capacity: usize, comparator: F) -> Heap<T>
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        Heap::with_kind(capacity, Kind::Custom, comparator)
+    }
+
+    fn with_kind<F>(capacity: usize, kind: Kind, comparator: F) -> Heap<T>
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
         Self {
             elements: Vec::with_capacity(capacity),
             size: 0,
-            parent_child_relation,
+            comparator: Box::new(comparator),
+            kind,
         }
     }
 
+    pub fn new_by_key<K, F>(capacity: usize, key: F) -> Heap<T>
+    where
+        K: Ord,
+        F: Fn(&T) -> K + 'static,
+    {
+        Heap::new_by(capacity, move |a, b| key(a).cmp(&key(b)))
+    }
+
+    pub fn from_vec<F>(elements: Vec<T>, comparator: F) -> Heap<T>
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        Heap::from_vec_with_kind(elements, Kind::Custom, comparator)
+    }
+
+    fn from_vec_with_kind<F>(elements: Vec<T>, kind: Kind, comparator: F) -> Heap<T>
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        let size = elements.len();
+        let mut heap = Heap {
+            elements,
+            size,
+            comparator: Box::new(comparator),
+            kind,
+        };
+        heap.heapify();
+        heap
+    }
+
     pub fn insert_all(&mut self, slice: &[T])
     where
         T: Clone,
@@ -69,7 +114,7 @@ impl<T: Ord> Heap<T> {
             self.elements[self.size] = new_t;
         }
         if self.size > 0 {
-            self.shift_up();
+            heap_core::shift_up(self, self.size);
         }
         self.size += 1;
     }
@@ -89,58 +134,48 @@ impl<T: Ord> Heap<T> {
         self.size -= 1;
         if self.size > 0 {
             self.elements.swap(0, self.size);
-            self.shift_down();
+            heap_core::shift_down(self, 0);
         }
         result
     }
 
-    fn shift_up(&mut self) {
-        let mut current_child = self.size;
-        let mut current_parent = parent_of(current_child);
-
-        while !self.heap_property_satisfied(current_parent, current_child) {
-            self.elements.swap(current_parent, current_child);
-            if current_parent == 0 {
-                break;
-            }
-            current_child = current_parent;
-            current_parent = parent_of(current_child);
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        self.elements.truncate(self.size);
+        while self.size > 1 {
+            self.size -= 1;
+            self.elements.swap(0, self.size);
+            heap_core::shift_down(&mut self, 0);
         }
+        self.elements
     }
 
-    fn shift_down(&mut self) {
-        let mut current_parent = 0;
-        loop {
-            let left_child = left_child_of(current_parent);
-            let right_child = right_child_of(current_parent);
-            if left_child >= self.size {
-                return;
-            }
-            let current_child = if right_child < self.size {
-                if self.heap_property_satisfied(left_child, right_child) {
-                    left_child
-                } else {
-                    right_child
-                }
-            } else {
-                left_child
-            };
-            if self.heap_property_satisfied(current_parent, current_child) {
-                return;
-            }
-            self.elements.swap(current_parent, current_child);
-            current_parent = current_child;
-        }
+    /// Combines `other` into `self`, re-heapifying under `self`'s comparator.
+    /// Cheaper than draining `other` element by element: both backing
+    /// vectors are concatenated and heapified once in O(n).
+    pub fn append(&mut self, other: Heap<T>) {
+        assert_eq!(
+            self.kind, other.kind,
+            "cannot meld heaps built with different orderings"
+        );
+        self.elements.truncate(self.size);
+        let mut other_elements = other.elements;
+        other_elements.truncate(other.size);
+        self.elements.append(&mut other_elements);
+        self.size = self.elements.len();
+        self.heapify();
     }
 
-    #[inline]
-    fn heap_property_satisfied(&self, parent_index: usize, child_index: usize) -> bool {
-        self.parent_child_relation
-            .rel(&self.elements[parent_index], &self.elements[child_index])
+    pub fn meld(mut self, other: Heap<T>) -> Heap<T> {
+        self.append(other);
+        self
+    }
+
+    fn heapify(&mut self) {
+        for parent_index in (0..=heap_core::last_parent_index(self.size)).rev() {
+            heap_core::shift_down(self, parent_index);
+        }
     }
-}
 
-impl<T> Heap<T> {
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.elements.iter().take(self.size)
     }
@@ -151,7 +186,7 @@ impl<T: Debug + Sized> Debug for Heap<T> {
         f.debug_struct("Heap")
             .field("elements", &&self.elements[0..self.size])
             .field("size", &self.size)
-            .field("parent_child_relation", &self.parent_child_relation)
+            .field("kind", &self.kind)
             .finish()
     }
 }
@@ -160,6 +195,7 @@ impl<T: Debug + Sized> Debug for Heap<T> {
 mod tests {
 
     use super::*;
+    use crate::heap_core::check_heap_property_for_all_parents_and_their_children;
     use proptest::prelude::*;
     use std::ops::Range;
 
@@ -209,19 +245,79 @@ mod tests {
             }
             check_heap_property_for_all_parents_and_their_children(&heap);
         }
-    }
 
-    fn check_heap_property_for_all_parents_and_their_children<T: Ord>(heap: &Heap<T>) {
-        let size = heap.size;
-        for parent_index in 0..heap.last_parent_index() {
-            let child_indices = [left_child_of(parent_index), right_child_of(parent_index)];
-            for child_index in child_indices.iter().cloned().filter(|c| *c < size) {
-                assert_eq!(
-                    heap.heap_property_satisfied(parent_index, child_index),
-                    true
-                );
+        #[test]
+        fn new_by_key_orders_elements_by_extracted_key(values in proptest::collection::vec(any::<i32>(), 0..1000)) {
+            let mut heap = Heap::new_by_key(values.len(), |v: &i32| v.unsigned_abs());
+            heap.insert_all(&values);
+            check_heap_property_for_all_parents_and_their_children(&heap);
+        }
+
+        #[test]
+        fn from_vec_should_satisfy_heap_property(values in proptest::collection::vec(any::<i32>(), 0..1000)) {
+            let heap = Heap::from_vec_min(values);
+            check_heap_property_for_all_parents_and_their_children(&heap);
+        }
+
+        #[test]
+        fn into_sorted_vec_should_equal_input_sorted_ascending_for_a_max_heap(values in proptest::collection::vec(any::<i32>(), 0..1000)) {
+            let mut expected = values.clone();
+            expected.sort();
+
+            let actual = Heap::from_vec_max(values).into_sorted_vec();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn into_sorted_vec_should_equal_input_sorted_descending_for_a_min_heap(values in proptest::collection::vec(any::<i32>(), 0..1000)) {
+            let mut expected = values.clone();
+            expected.sort_by(|a, b| b.cmp(a));
+
+            let actual = Heap::from_vec_min(values).into_sorted_vec();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn into_sorted_vec_should_not_include_stale_entries_left_by_extract_top(values in proptest::collection::vec(any::<i32>(), 1..1000)) {
+            let mut heap = Heap::from_vec_max(values.clone());
+            let extracted = heap.extract_top();
+
+            let mut expected: Vec<i32> = values;
+            if let Some(extracted) = extracted {
+                let position = expected.iter().position(|v| *v == extracted).unwrap();
+                expected.remove(position);
             }
+            expected.sort();
+
+            assert_eq!(heap.into_sorted_vec(), expected);
         }
+
+        #[test]
+        fn melding_two_heaps_should_satisfy_heap_property_and_union_their_elements(
+            left in proptest::collection::vec(any::<i32>(), 0..500),
+            right in proptest::collection::vec(any::<i32>(), 0..500),
+        ) {
+            let mut expected: Vec<i32> = left.iter().chain(right.iter()).cloned().collect();
+            expected.sort();
+
+            let melded = Heap::from_vec_min(left).meld(Heap::from_vec_min(right));
+            check_heap_property_for_all_parents_and_their_children(&melded);
+
+            let mut actual: Vec<i32> = melded.iter().cloned().collect();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot meld heaps built with different orderings")]
+    fn melding_a_min_heap_with_a_max_heap_panics() {
+        let min_heap = Heap::<i32>::new_min(4);
+        let max_heap = Heap::<i32>::new_max(4);
+
+        min_heap.meld(max_heap);
     }
 
     fn any_heap<T>(size: Range<usize>) -> impl Strategy<Value = Heap<T>>
@@ -235,27 +331,21 @@ mod tests {
     where
         T: Arbitrary + Ord + Clone,
     {
-        any_heap_with_rel(size, ParentChildRelation::Smaller)
+        proptest::collection::vec(any::<T>(), size).prop_map(move |v| {
+            let mut min_heap = Heap::new_min(v.len());
+            min_heap.insert_all(&v);
+            min_heap
+        })
     }
 
     fn any_max_heap<T>(size: Range<usize>) -> impl Strategy<Value = Heap<T>>
-    where
-        T: Arbitrary + Ord + Clone,
-    {
-        any_heap_with_rel(size, ParentChildRelation::Greater)
-    }
-
-    fn any_heap_with_rel<T>(
-        size: Range<usize>,
-        relation: ParentChildRelation,
-    ) -> impl Strategy<Value = Heap<T>>
     where
         T: Arbitrary + Ord + Clone,
     {
         proptest::collection::vec(any::<T>(), size).prop_map(move |v| {
-            let mut min_heap = Heap::new(v.len(), relation);
-            min_heap.insert_all(&v);
-            min_heap
+            let mut max_heap = Heap::new_max(v.len());
+            max_heap.insert_all(&v);
+            max_heap
         })
     }
 
@@ -283,11 +373,6 @@ mod tests {
     }
 
     impl<T> Heap<T> {
-        #[inline]
-        fn last_parent_index(&self) -> usize {
-            self.size.checked_sub(1).unwrap_or_default() / 2
-        }
-
         pub fn occurrence_of(&self, item: Option<&T>) -> usize
         where
             T: Eq,
@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+pub(crate) type Comparator<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
+#[inline]
+pub(crate) fn parent_of(child_index: usize) -> usize {
+    child_index.checked_sub(1).unwrap_or_default() / 2
+}
+
+#[inline]
+pub(crate) fn left_child_of(parent_index: usize) -> usize {
+    parent_index * 2 + 1
+}
+
+#[inline]
+pub(crate) fn right_child_of(parent_index: usize) -> usize {
+    parent_index * 2 + 2
+}
+
+pub(crate) trait HeapStore<T> {
+    fn len(&self) -> usize;
+    fn swap(&mut self, i: usize, j: usize);
+    fn satisfies(&self, parent_index: usize, child_index: usize) -> bool;
+}
+
+pub(crate) fn shift_up<T, S: HeapStore<T> + ?Sized>(store: &mut S, start: usize) {
+    let mut current_child = start;
+    let mut current_parent = parent_of(current_child);
+
+    while !store.satisfies(current_parent, current_child) {
+        store.swap(current_parent, current_child);
+        if current_parent == 0 {
+            break;
+        }
+        current_child = current_parent;
+        current_parent = parent_of(current_child);
+    }
+}
+
+pub(crate) fn shift_down<T, S: HeapStore<T> + ?Sized>(store: &mut S, start: usize) {
+    let mut current_parent = start;
+    loop {
+        let left_child = left_child_of(current_parent);
+        let right_child = right_child_of(current_parent);
+        if left_child >= store.len() {
+            return;
+        }
+        let current_child = if right_child < store.len() {
+            if store.satisfies(left_child, right_child) {
+                left_child
+            } else {
+                right_child
+            }
+        } else {
+            left_child
+        };
+        if store.satisfies(current_parent, current_child) {
+            return;
+        }
+        store.swap(current_parent, current_child);
+        current_parent = current_child;
+    }
+}
+
+#[inline]
+pub(crate) fn last_parent_index(size: usize) -> usize {
+    size.checked_sub(1).unwrap_or_default() / 2
+}
+
+#[cfg(test)]
+pub(crate) fn check_heap_property_for_all_parents_and_their_children<T, S: HeapStore<T> + ?Sized>(
+    store: &S,
+) {
+    let size = store.len();
+    for parent_index in 0..=last_parent_index(size) {
+        let child_indices = [left_child_of(parent_index), right_child_of(parent_index)];
+        for child_index in child_indices.iter().cloned().filter(|c| *c < size) {
+            assert_eq!(store.satisfies(parent_index, child_index), true);
+        }
+    }
+}
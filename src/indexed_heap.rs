@@ -0,0 +1,199 @@
+use crate::heap_core::{self, Comparator, HeapStore};
+use std::cmp::Ordering;
+
+pub type Handle = usize;
+
+const REMOVED: usize = usize::MAX;
+
+pub struct IndexedHeap<T> {
+    elements: Vec<T>,
+    handle_of_slot: Vec<Handle>,
+    positions: Vec<usize>,
+    size: usize,
+    comparator: Comparator<T>,
+}
+
+impl<T> HeapStore<T> for IndexedHeap<T> {
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.elements.swap(i, j);
+        self.handle_of_slot.swap(i, j);
+        self.positions[self.handle_of_slot[i]] = i;
+        self.positions[self.handle_of_slot[j]] = j;
+    }
+
+    fn satisfies(&self, parent_index: usize, child_index: usize) -> bool {
+        (self.comparator)(&self.elements[parent_index], &self.elements[child_index])
+            != Ordering::Less
+    }
+}
+
+impl<T: Ord> IndexedHeap<T> {
+    pub fn new_min(capacity: usize) -> IndexedHeap<T> {
+        IndexedHeap::new_by(capacity, |a: &T, b: &T| b.cmp(a))
+    }
+
+    pub fn new_max(capacity: usize) -> IndexedHeap<T> {
+        IndexedHeap::new_by(capacity, |a: &T, b: &T| a.cmp(b))
+    }
+}
+
+impl<T> IndexedHeap<T> {
+    pub fn new_by<F>(capacity: usize, comparator: F) -> IndexedHeap<T>
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        Self {
+            elements: Vec::with_capacity(capacity),
+            handle_of_slot: Vec::with_capacity(capacity),
+            positions: Vec::with_capacity(capacity),
+            size: 0,
+            comparator: Box::new(comparator),
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> Handle {
+        let handle = self.positions.len();
+        let slot = self.size;
+        if slot == self.elements.len() {
+            self.elements.push(value);
+            self.handle_of_slot.push(handle);
+        } else {
+            self.elements[slot] = value;
+            self.handle_of_slot[slot] = handle;
+        }
+        self.positions.push(slot);
+        self.size += 1;
+        if slot > 0 {
+            heap_core::shift_up(self, slot);
+        }
+        handle
+    }
+
+    pub fn change_priority(&mut self, handle: Handle, new_value: T) {
+        let slot = self.slot_of(handle);
+        let moves_toward_top =
+            (self.comparator)(&new_value, &self.elements[slot]) == Ordering::Greater;
+        self.elements[slot] = new_value;
+        if moves_toward_top {
+            heap_core::shift_up(self, slot);
+        } else {
+            heap_core::shift_down(self, slot);
+        }
+    }
+
+    /// Removes the element identified by `handle`. `handle` is invalidated
+    /// by this call; using it again (with `get`, `change_priority`, or
+    /// `remove`) panics rather than silently reading the slot of whatever
+    /// later took its place.
+    pub fn remove(&mut self, handle: Handle) -> T {
+        let slot = self.slot_of(handle);
+        let last = self.size - 1;
+        self.swap(slot, last);
+        self.size -= 1;
+        let removed = self.elements.pop().expect("heap should not be empty");
+        self.handle_of_slot.pop();
+        self.positions[handle] = REMOVED;
+        if slot < self.size {
+            heap_core::shift_up(self, slot);
+            heap_core::shift_down(self, slot);
+        }
+        removed
+    }
+
+    pub fn find_top(&self) -> Option<&T> {
+        if self.size == 0 {
+            None
+        } else {
+            self.elements.first()
+        }
+    }
+
+    pub fn get(&self, handle: Handle) -> &T {
+        &self.elements[self.slot_of(handle)]
+    }
+
+    fn slot_of(&self, handle: Handle) -> usize {
+        let slot = self.positions[handle];
+        assert_ne!(slot, REMOVED, "handle {handle} refers to a removed element");
+        slot
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.elements.iter().take(self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::heap_core::check_heap_property_for_all_parents_and_their_children;
+    use proptest::prelude::*;
+    use std::ops::Range;
+
+    proptest! {
+        #[test]
+        fn heap_property_should_hold_after_random_change_priority_calls(
+            values in proptest::collection::vec(any::<i32>(), 1..200),
+            ops in any_change_priority_seq(0..200),
+        ) {
+            let mut heap = IndexedHeap::new_min(values.len());
+            let handles: Vec<_> = values.iter().cloned().map(|v| heap.insert(v)).collect();
+
+            for (handle_index, new_value) in ops {
+                let handle = handles[handle_index % handles.len()];
+                heap.change_priority(handle, new_value);
+                check_heap_property_for_all_parents_and_their_children(&heap);
+            }
+        }
+
+        #[test]
+        fn handle_always_resolves_to_the_mutated_value(values in proptest::collection::vec(any::<i32>(), 1..200), new_value in any::<i32>()) {
+            let mut heap = IndexedHeap::new_min(values.len());
+            let handles: Vec<_> = values.iter().cloned().map(|v| heap.insert(v)).collect();
+            let handle = handles[0];
+
+            heap.change_priority(handle, new_value);
+
+            assert_eq!(heap.get(handle), &new_value);
+        }
+
+        #[test]
+        fn removing_random_handles_should_preserve_heap_property(
+            values in proptest::collection::vec(any::<i32>(), 1..200),
+            removal_picks in proptest::collection::vec(any::<usize>(), 0..200),
+        ) {
+            let mut heap = IndexedHeap::new_min(values.len());
+            let mut handles: Vec<_> = values.iter().cloned().map(|v| heap.insert(v)).collect();
+
+            for pick in removal_picks {
+                if handles.is_empty() {
+                    break;
+                }
+                let handle = handles.swap_remove(pick % handles.len());
+                heap.remove(handle);
+                check_heap_property_for_all_parents_and_their_children(&heap);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "refers to a removed element")]
+    fn using_a_removed_handle_panics() {
+        let mut heap = IndexedHeap::new_min(4);
+        let handle = heap.insert(1);
+        heap.insert(2);
+
+        heap.remove(handle);
+
+        heap.get(handle);
+    }
+
+    fn any_change_priority_seq(size: Range<usize>) -> impl Strategy<Value = Vec<(usize, i32)>> {
+        proptest::collection::vec((any::<usize>(), any::<i32>()), size)
+    }
+}